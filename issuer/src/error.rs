@@ -0,0 +1,88 @@
+use std::fmt;
+use std::io;
+
+use crate::format::N_ATTRS;
+
+#[derive(Debug)]
+pub enum IssuerError {
+    Usage(String),
+    InvalidField(String),
+    PoseidonArity(usize),
+    PublicInput(Vec<String>),
+    Unsatisfiable(String),
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    Json {
+        path: String,
+        source: serde_json::Error,
+    },
+    MalformedProof(String),
+    ProofRejected,
+    CircuitArity {
+        attrs: usize,
+        weights: usize,
+    },
+    MalformedCircuit(String),
+}
+
+impl IssuerError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            IssuerError::Usage(_) => 2,
+            IssuerError::InvalidField(_) => 3,
+            IssuerError::PoseidonArity(_) => 4,
+            IssuerError::PublicInput(_) => 5,
+            IssuerError::Unsatisfiable(_) => 6,
+            IssuerError::Io { .. } => 7,
+            IssuerError::Json { .. } => 8,
+            IssuerError::MalformedProof(_) => 9,
+            IssuerError::ProofRejected => 10,
+            IssuerError::CircuitArity { .. } => 11,
+            IssuerError::MalformedCircuit(_) => 12,
+        }
+    }
+}
+
+impl fmt::Display for IssuerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IssuerError::Usage(msg) => write!(f, "{}", msg),
+            IssuerError::InvalidField(flag) => {
+                write!(f, "{} holds a value that is not a field element", flag)
+            }
+            IssuerError::PoseidonArity(n) => {
+                write!(f, "poseidon supports 1 to 5 inputs, got {}", n)
+            }
+            IssuerError::PublicInput(names) => write!(
+                f,
+                "{} would be public inputs (use --allow-public to override)",
+                names.join(", ")
+            ),
+            IssuerError::Unsatisfiable(reason) => {
+                write!(f, "inputs cannot satisfy the circuit: {}", reason)
+            }
+            IssuerError::Io { path, source } => write!(f, "{}: {}", path, source),
+            IssuerError::Json { path, source } => write!(f, "{}: {}", path, source),
+            IssuerError::MalformedProof(what) => write!(f, "malformed proof artifact: {}", what),
+            IssuerError::ProofRejected => write!(f, "proof rejected"),
+            IssuerError::CircuitArity { attrs, weights } => write!(
+                f,
+                "the circuit takes {} attrs and {} weights, got {} and {}",
+                N_ATTRS, N_ATTRS, attrs, weights
+            ),
+            IssuerError::MalformedCircuit(what) => write!(f, "malformed circuit: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for IssuerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IssuerError::Io { source, .. } => Some(source),
+            IssuerError::Json { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+use crate::error::IssuerError;
+use crate::poseidon;
+
+// n in `component main = MultiAttributeProof(4)`.
+pub const N_ATTRS: usize = 4;
+
+// Mirrors the inputs of MultiAttributeProof in circuits/attributeProof.circom.
+pub struct CircuitInput {
+    pub attrs: Vec<Fr>,
+    pub r: Fr,
+    pub weights: Vec<Fr>,
+    pub threshold: Fr,
+}
+
+// PrimeField::from_str reduces modulo the field order, so a value >= p
+// would silently become a different element. Only plain decimal digits
+// below p are accepted.
+pub fn canonical<F: PrimeField>(s: &str) -> Option<F> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    F::from_bigint(BigUint::from_str(s).ok()?.try_into().ok()?)
+}
+
+// Fr's Display trims leading zeros, which renders zero as "".
+fn decimal(value: &Fr) -> String {
+    value.into_bigint().to_string()
+}
+
+fn decimals(values: &[Fr]) -> Vec<String> {
+    values.iter().map(decimal).collect()
+}
+
+impl CircuitInput {
+    pub fn check_arity(&self) -> Result<(), IssuerError> {
+        if self.attrs.len() != N_ATTRS || self.weights.len() != N_ATTRS {
+            return Err(IssuerError::CircuitArity {
+                attrs: self.attrs.len(),
+                weights: self.weights.len(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn commitment(&self) -> Result<Fr, IssuerError> {
+        self.check_arity()?;
+        let mut preimage = self.attrs.clone();
+        preimage.push(self.r);
+        poseidon::hash(&preimage)
+    }
+
+    // snarkjs input format: every field element as a decimal string.
+    pub fn to_json(&self) -> Result<Value, IssuerError> {
+        Ok(json!({
+            "attrs": decimals(&self.attrs),
+            "commitment": decimal(&self.commitment()?),
+            "r": decimal(&self.r),
+            "threshold": decimal(&self.threshold),
+            "weights": decimals(&self.weights)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(attrs: [u64; 4], weights: [u64; 4]) -> CircuitInput {
+        CircuitInput {
+            attrs: attrs.iter().map(|a| Fr::from(*a)).collect(),
+            r: Fr::from(5u64),
+            weights: weights.iter().map(|w| Fr::from(*w)).collect(),
+            threshold: Fr::from(15u64),
+        }
+    }
+
+    #[test]
+    fn to_json_matches_snarkjs_format() {
+        let json = input([10, 12, 3, 1], [1, 1, 0, 0]).to_json().unwrap();
+
+        assert_eq!(json["attrs"], json!(["10", "12", "3", "1"]));
+        assert_eq!(json["r"], "5");
+        assert_eq!(json["threshold"], "15");
+        assert_eq!(json["weights"], json!(["1", "1", "0", "0"]));
+    }
+
+    #[test]
+    fn zero_is_rendered_as_0() {
+        let json = input([0, 0, 0, 0], [0, 0, 0, 0]).to_json().unwrap();
+
+        assert_eq!(json["attrs"], json!(["0", "0", "0", "0"]));
+        assert_eq!(json["weights"], json!(["0", "0", "0", "0"]));
+    }
+
+    #[test]
+    fn large_values_keep_every_digit() {
+        let mut inp = input([10, 12, 3, 1], [1, 1, 0, 0]);
+        inp.attrs[0] = -Fr::from(1u64);
+
+        assert_eq!(
+            inp.to_json().unwrap()["attrs"][0],
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616"
+        );
+    }
+
+    #[test]
+    fn commitment_matches_circomlibjs() {
+        // node poseidon_multi.js
+        let json = input([10, 12, 3, 1], [1, 1, 0, 0]).to_json().unwrap();
+
+        assert_eq!(
+            json["commitment"],
+            "14859332792076484157671157271666106058927871584252538330311074070994105552513"
+        );
+    }
+
+    #[test]
+    fn wrong_arity_is_rejected_before_hashing() {
+        let mut inp = input([10, 12, 3, 1], [1, 1, 0, 0]);
+        inp.attrs.push(Fr::from(7u64));
+        inp.weights.push(Fr::from(1u64));
+
+        assert!(matches!(
+            inp.to_json(),
+            Err(IssuerError::CircuitArity {
+                attrs: 5,
+                weights: 5
+            })
+        ));
+
+        inp.attrs.truncate(3);
+        inp.weights.truncate(3);
+        assert!(matches!(
+            inp.to_json(),
+            Err(IssuerError::CircuitArity {
+                attrs: 3,
+                weights: 3
+            })
+        ));
+    }
+}
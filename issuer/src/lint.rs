@@ -0,0 +1,198 @@
+use serde_json::Value;
+
+use crate::error::IssuerError;
+
+// Inputs carrying raw attribute data or blinding; these must stay private.
+const PRIVATE_SIGNALS: [&str; 2] = ["attrs", "r"];
+
+fn is_ident(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn malformed(what: &str) -> IssuerError {
+    IssuerError::MalformedCircuit(what.to_string())
+}
+
+// Strips `token` from the front of `s` only when it is a whole identifier.
+fn strip_token<'a>(s: &'a str, token: &str) -> Option<&'a str> {
+    s.strip_prefix(token)
+        .filter(|rest| !rest.starts_with(is_ident))
+}
+
+// Text following `component main`, with any whitespace between the tokens.
+fn main_declaration(circuit: &str) -> Option<&str> {
+    circuit.match_indices("component").find_map(|(i, _)| {
+        if circuit[..i].ends_with(is_ident) {
+            return None;
+        }
+        let after = strip_token(&circuit[i..], "component")?;
+        let name = after.trim_start();
+        if name.len() == after.len() {
+            return None;
+        }
+        strip_token(name, "main")
+    })
+}
+
+// Fails closed: anything other than `= ...` or a well-formed
+// `{public [a, b]} = ...` after `component main` is an error, so a parse
+// miss can never be mistaken for "no public inputs".
+pub fn declared_public_inputs(circuit: &str) -> Result<Vec<String>, IssuerError> {
+    let decl = main_declaration(circuit)
+        .ok_or_else(|| malformed("no `component main` declaration"))?
+        .trim_start();
+
+    if decl.starts_with('=') {
+        return Ok(Vec::new());
+    }
+
+    let bad_list = || malformed("expected `{public [...]}` after `component main`");
+
+    let list = decl
+        .strip_prefix('{')
+        .and_then(|s| strip_token(s.trim_start(), "public"))
+        .and_then(|s| s.trim_start().strip_prefix('['))
+        .ok_or_else(bad_list)?;
+    let (list, rest) = list.split_once(']').ok_or_else(bad_list)?;
+    rest.trim_start()
+        .strip_prefix('}')
+        .filter(|s| s.trim_start().starts_with('='))
+        .ok_or_else(bad_list)?;
+
+    if list.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    list.split(',')
+        .map(str::trim)
+        .map(|name| {
+            if !name.is_empty() && name.chars().all(is_ident) {
+                Ok(name.to_string())
+            } else {
+                Err(bad_list())
+            }
+        })
+        .collect()
+}
+
+pub fn public_leaks(input: &Value, circuit: &str) -> Result<Vec<String>, IssuerError> {
+    let public = declared_public_inputs(circuit)?;
+
+    Ok(PRIVATE_SIGNALS
+        .iter()
+        .filter(|name| input.get(**name).is_some())
+        .filter(|name| public.iter().any(|p| p == *name))
+        .map(|name| name.to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const BODY: &str = "template T(n) { signal input attrs[n]; signal input r; }\n";
+
+    fn input() -> Value {
+        json!({"attrs": ["10", "12", "3", "1"], "r": "5", "threshold": "15"})
+    }
+
+    fn public(decl: &str) -> Result<Vec<String>, IssuerError> {
+        declared_public_inputs(&format!("{}{}", BODY, decl))
+    }
+
+    fn is_malformed(r: Result<Vec<String>, IssuerError>) -> bool {
+        matches!(r, Err(IssuerError::MalformedCircuit(_)))
+    }
+
+    #[test]
+    fn parses_public_list() {
+        assert_eq!(
+            public("component main {public [attrs, r]} = T(4);").unwrap(),
+            vec!["attrs", "r"]
+        );
+    }
+
+    #[test]
+    fn spacing_between_tokens_does_not_matter() {
+        for decl in [
+            "component  main {public [attrs]} = T(4);",
+            "component\tmain{public[attrs]}=T(4);",
+            "component\n  main\n{ public\n[ attrs ] }\n= T(4);",
+        ] {
+            assert_eq!(public(decl).unwrap(), vec!["attrs"], "{:?}", decl);
+        }
+    }
+
+    #[test]
+    fn no_public_declaration() {
+        assert!(public("component main = T(4);").unwrap().is_empty());
+        assert!(public("component main {public []} = T(4);")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn ignores_public_after_the_main_declaration() {
+        assert!(public("component main = T(4);\n// {public [attrs]}")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn other_components_are_not_main() {
+        assert!(is_malformed(public("component mainly = T(4);")));
+        assert!(is_malformed(public("subcomponent main = T(4);")));
+    }
+
+    #[test]
+    fn rejects_input_without_main_component() {
+        assert!(is_malformed(declared_public_inputs(include_str!(
+            "../../demo.sh"
+        ))));
+        assert!(is_malformed(declared_public_inputs("")));
+    }
+
+    #[test]
+    fn rejects_malformed_public_list() {
+        for decl in [
+            "component main {public [attrs, r} = T(4);",
+            "component main {public attrs} = T(4);",
+            "component main {publik [attrs]} = T(4);",
+            "component main {public [attrs,, r]} = T(4);",
+            "component main {public [attrs]} T(4);",
+            "component main T(4);",
+        ] {
+            assert!(is_malformed(public(decl)), "{:?}", decl);
+        }
+    }
+
+    #[test]
+    fn leaks_only_private_signals_that_are_public() {
+        let circuit = format!(
+            "{}component main {{public [threshold, attrs]}} = T(4);",
+            BODY
+        );
+
+        assert_eq!(public_leaks(&input(), &circuit).unwrap(), vec!["attrs"]);
+    }
+
+    #[test]
+    fn no_leaks_without_overlap() {
+        let circuit = format!("{}component main {{public [threshold]}} = T(4);", BODY);
+
+        assert!(public_leaks(&input(), &circuit).unwrap().is_empty());
+    }
+
+    #[test]
+    fn malformed_circuit_is_not_a_pass() {
+        assert!(public_leaks(&input(), "echo hi").is_err());
+    }
+
+    #[test]
+    fn shipped_circuit_has_no_leaks() {
+        let circuit = include_str!("../../circuits/attributeProof.circom");
+
+        assert!(public_leaks(&input(), circuit).unwrap().is_empty());
+    }
+}
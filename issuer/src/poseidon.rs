@@ -0,0 +1,124 @@
+use ark_bn254::Fr;
+use ark_ff::Field;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::error::IssuerError;
+use crate::poseidon_constants::{C, M};
+
+// Same parameters as circomlib's poseidon.circom.
+const N_ROUNDS_F: usize = 8;
+const N_ROUNDS_P: [usize; 5] = [56, 57, 56, 60, 60];
+
+struct Params {
+    c: Vec<Fr>,
+    m: Vec<Vec<Fr>>,
+}
+
+fn fr(s: &str) -> Fr {
+    Fr::from_str(s).unwrap()
+}
+
+fn params() -> &'static [Params] {
+    static PARAMS: OnceLock<Vec<Params>> = OnceLock::new();
+
+    PARAMS.get_or_init(|| {
+        C.iter()
+            .zip(M.iter())
+            .map(|(c, m)| Params {
+                c: c.iter().map(|s| fr(s)).collect(),
+                m: m.iter()
+                    .map(|row| row.iter().map(|s| fr(s)).collect())
+                    .collect(),
+            })
+            .collect()
+    })
+}
+
+fn pow5(a: Fr) -> Fr {
+    a.square().square() * a
+}
+
+pub fn hash(inputs: &[Fr]) -> Result<Fr, IssuerError> {
+    if inputs.is_empty() || inputs.len() > N_ROUNDS_P.len() {
+        return Err(IssuerError::PoseidonArity(inputs.len()));
+    }
+
+    let t = inputs.len() + 1;
+    let n_rounds_p = N_ROUNDS_P[t - 2];
+    let Params { c, m } = &params()[t - 2];
+
+    let mut state = vec![Fr::from(0u64)];
+    state.extend_from_slice(inputs);
+
+    for r in 0..N_ROUNDS_F + n_rounds_p {
+        for (i, a) in state.iter_mut().enumerate() {
+            *a += c[r * t + i];
+        }
+
+        if r < N_ROUNDS_F / 2 || r >= N_ROUNDS_F / 2 + n_rounds_p {
+            for a in state.iter_mut() {
+                *a = pow5(*a);
+            }
+        } else {
+            state[0] = pow5(state[0]);
+        }
+
+        state = m
+            .iter()
+            .map(|row| row.iter().zip(&state).map(|(m, a)| *m * a).sum())
+            .collect();
+    }
+
+    Ok(state[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_u64(inputs: &[u64]) -> Fr {
+        let inputs: Vec<Fr> = inputs.iter().map(|i| Fr::from(*i)).collect();
+        hash(&inputs).unwrap()
+    }
+
+    // Known answers from circomlibjs buildPoseidon().
+    #[test]
+    fn matches_circomlibjs_for_every_arity() {
+        let cases: [(&[u64], &str); 5] = [
+            (
+                &[1],
+                "18586133768512220936620570745912940619677854269274689475585506675881198879027",
+            ),
+            (
+                &[1, 2],
+                "7853200120776062878684798364095072458815029376092732009249414926327459813530",
+            ),
+            (
+                &[1, 2, 3],
+                "6542985608222806190361240322586112750744169038454362455181422643027100751666",
+            ),
+            (
+                &[7, 8, 9, 10],
+                "1373032492133357216471697558924019385907923963409748514428684374475873678634",
+            ),
+            (
+                &[10, 12, 3, 1, 5],
+                "14859332792076484157671157271666106058927871584252538330311074070994105552513",
+            ),
+        ];
+
+        for (inputs, expected) in cases {
+            assert_eq!(hash_u64(inputs), fr(expected), "poseidon({:?})", inputs);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_arity() {
+        assert!(matches!(hash(&[]), Err(IssuerError::PoseidonArity(0))));
+        assert!(matches!(
+            hash(&[Fr::from(1u64); 6]),
+            Err(IssuerError::PoseidonArity(6))
+        ));
+    }
+}
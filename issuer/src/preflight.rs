@@ -0,0 +1,119 @@
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+
+use crate::error::IssuerError;
+use crate::format::CircuitInput;
+
+// Matches the Num2Bits(64) range guards in the circuit.
+const RANGE_BITS: u32 = 64;
+
+fn in_range(v: &Fr) -> Option<u64> {
+    let v = v.into_bigint();
+    (v.num_bits() <= RANGE_BITS).then(|| v.as_ref()[0])
+}
+
+// Replays the circuit: the weighted sum is taken in the field, then both
+// it and the threshold must pass the 64-bit guards before the comparison.
+pub fn check(input: &CircuitInput) -> Result<(), IssuerError> {
+    input.check_arity()?;
+
+    let sum: Fr = input
+        .attrs
+        .iter()
+        .zip(&input.weights)
+        .map(|(a, w)| *a * w)
+        .sum();
+
+    let unsatisfiable = |reason: String| Err(IssuerError::Unsatisfiable(reason));
+
+    let Some(sum) = in_range(&sum) else {
+        return unsatisfiable(format!("weighted sum does not fit in {} bits", RANGE_BITS));
+    };
+    let Some(threshold) = in_range(&input.threshold) else {
+        return unsatisfiable(format!("threshold does not fit in {} bits", RANGE_BITS));
+    };
+    if sum < threshold {
+        // The sum is not printed: with a unit weight it is the raw attribute.
+        return unsatisfiable(format!("weighted sum is below threshold {}", threshold));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::Field;
+
+    fn input(attrs: [Fr; 4], weights: [u64; 4], threshold: u64) -> CircuitInput {
+        CircuitInput {
+            attrs: attrs.to_vec(),
+            r: Fr::from(5u64),
+            weights: weights.iter().map(|w| Fr::from(*w)).collect(),
+            threshold: Fr::from(threshold),
+        }
+    }
+
+    fn small(attrs: [u64; 4]) -> [Fr; 4] {
+        attrs.map(Fr::from)
+    }
+
+    #[test]
+    fn accepts_satisfiable_inputs() {
+        assert!(check(&input(small([10, 12, 3, 1]), [1, 1, 0, 0], 15)).is_ok());
+        assert!(check(&input(small([10, 12, 3, 1]), [1, 1, 0, 0], 22)).is_ok());
+        assert!(check(&input(small([0, 0, 0, 0]), [0, 0, 0, 0], 0)).is_ok());
+    }
+
+    #[test]
+    fn rejects_sum_below_threshold() {
+        let err = check(&input(small([10, 12, 3, 1]), [1, 1, 0, 0], 23)).unwrap_err();
+
+        assert!(matches!(err, IssuerError::Unsatisfiable(_)));
+        assert!(!err.to_string().contains("22"), "sum leaked: {}", err);
+    }
+
+    #[test]
+    fn unweighted_attrs_may_be_any_field_element() {
+        let huge = Fr::from(2u64).pow([200]);
+
+        assert!(check(&input(
+            [huge, Fr::from(20u64), Fr::from(0u64), Fr::from(0u64)],
+            [0, 1, 0, 0],
+            15
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn sum_is_taken_in_the_field() {
+        // (p - 1) + 16 wraps to 15.
+        let attrs = [
+            -Fr::from(1u64),
+            Fr::from(16u64),
+            Fr::from(0u64),
+            Fr::from(0u64),
+        ];
+
+        assert!(check(&input(attrs, [1, 1, 0, 0], 15)).is_ok());
+        assert!(check(&input(attrs, [1, 1, 0, 0], 16)).is_err());
+    }
+
+    #[test]
+    fn rejects_sum_outside_64_bits() {
+        let attrs = small([u64::MAX, 1, 0, 0]);
+
+        assert!(matches!(
+            check(&input(attrs, [1, 1, 0, 0], 1)),
+            Err(IssuerError::Unsatisfiable(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_threshold_outside_64_bits() {
+        let mut inp = input(small([10, 12, 3, 1]), [1, 1, 0, 0], 0);
+        inp.threshold = Fr::from(u64::MAX) + Fr::from(1u64);
+
+        assert!(matches!(check(&inp), Err(IssuerError::Unsatisfiable(_))));
+    }
+}
@@ -0,0 +1,326 @@
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, Zero};
+use serde_json::Value;
+use std::fs;
+
+use crate::error::IssuerError;
+use crate::format::canonical;
+
+pub struct VerifyingKey {
+    alpha_1: G1Affine,
+    beta_2: G2Affine,
+    gamma_2: G2Affine,
+    delta_2: G2Affine,
+    ic: Vec<G1Affine>,
+}
+
+pub struct Proof {
+    a: G1Affine,
+    b: G2Affine,
+    c: G1Affine,
+}
+
+fn malformed(what: &str) -> IssuerError {
+    IssuerError::MalformedProof(what.to_string())
+}
+
+pub fn read_json(path: &str) -> Result<Value, IssuerError> {
+    let text = fs::read_to_string(path).map_err(|source| IssuerError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    serde_json::from_str(&text).map_err(|source| IssuerError::Json {
+        path: path.to_string(),
+        source,
+    })
+}
+
+// snarkjs rejects values >= p; accepting them would make proofs and
+// signals malleable.
+fn fq(v: &Value, what: &str) -> Result<Fq, IssuerError> {
+    v.as_str()
+        .and_then(canonical)
+        .ok_or_else(|| malformed(what))
+}
+
+fn coords<'a>(v: &'a Value, n: usize, what: &str) -> Result<&'a [Value], IssuerError> {
+    v.as_array()
+        .filter(|c| c.len() == n)
+        .map(Vec::as_slice)
+        .ok_or_else(|| malformed(what))
+}
+
+fn fq2(v: &Value, what: &str) -> Result<Fq2, IssuerError> {
+    let c = coords(v, 2, what)?;
+    Ok(Fq2::new(fq(&c[0], what)?, fq(&c[1], what)?))
+}
+
+// snarkjs writes points as exactly [x, y, z] with z = 1. The point at
+// infinity is only accepted as [0, 1, 0]; any other z is a re-encoding of
+// the same proof and is rejected.
+fn g1(v: &Value, what: &str) -> Result<G1Affine, IssuerError> {
+    let c = coords(v, 3, what)?;
+    let (x, y, z) = (fq(&c[0], what)?, fq(&c[1], what)?, fq(&c[2], what)?);
+
+    if z.is_zero() && x.is_zero() && y.is_one() {
+        return Ok(G1Affine::zero());
+    }
+    if !z.is_one() {
+        return Err(malformed(what));
+    }
+    let p = G1Affine::new_unchecked(x, y);
+    if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(malformed(what));
+    }
+    Ok(p)
+}
+
+fn g2(v: &Value, what: &str) -> Result<G2Affine, IssuerError> {
+    let c = coords(v, 3, what)?;
+    let (x, y, z) = (fq2(&c[0], what)?, fq2(&c[1], what)?, fq2(&c[2], what)?);
+
+    if z.is_zero() && x.is_zero() && y.is_one() {
+        return Ok(G2Affine::zero());
+    }
+    if !z.is_one() {
+        return Err(malformed(what));
+    }
+    let p = G2Affine::new_unchecked(x, y);
+    if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(malformed(what));
+    }
+    Ok(p)
+}
+
+fn check_header(v: &Value, what: &str) -> Result<(), IssuerError> {
+    if v["protocol"] != "groth16" || v["curve"] != "bn128" {
+        return Err(malformed(&format!(
+            "{} is not a groth16/bn128 artifact",
+            what
+        )));
+    }
+    Ok(())
+}
+
+impl VerifyingKey {
+    pub fn from_json(v: &Value) -> Result<Self, IssuerError> {
+        check_header(v, "verification key")?;
+
+        let ic = v["IC"]
+            .as_array()
+            .ok_or_else(|| malformed("IC"))?
+            .iter()
+            .map(|p| g1(p, "IC"))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if ic.is_empty() || v["nPublic"].as_u64() != Some(ic.len() as u64 - 1) {
+            return Err(malformed("nPublic does not match IC"));
+        }
+
+        Ok(VerifyingKey {
+            alpha_1: g1(&v["vk_alpha_1"], "vk_alpha_1")?,
+            beta_2: g2(&v["vk_beta_2"], "vk_beta_2")?,
+            gamma_2: g2(&v["vk_gamma_2"], "vk_gamma_2")?,
+            delta_2: g2(&v["vk_delta_2"], "vk_delta_2")?,
+            ic,
+        })
+    }
+}
+
+impl Proof {
+    pub fn from_json(v: &Value) -> Result<Self, IssuerError> {
+        check_header(v, "proof")?;
+
+        Ok(Proof {
+            a: g1(&v["pi_a"], "pi_a")?,
+            b: g2(&v["pi_b"], "pi_b")?,
+            c: g1(&v["pi_c"], "pi_c")?,
+        })
+    }
+}
+
+pub fn public_inputs(v: &Value) -> Result<Vec<Fr>, IssuerError> {
+    v.as_array()
+        .ok_or_else(|| malformed("public signals"))?
+        .iter()
+        .map(|s| {
+            s.as_str()
+                .and_then(canonical)
+                .ok_or_else(|| malformed("public signals"))
+        })
+        .collect()
+}
+
+// e(A, B) = e(alpha, beta) * e(vk_x, gamma) * e(C, delta)
+pub fn verify(vk: &VerifyingKey, proof: &Proof, public: &[Fr]) -> Result<(), IssuerError> {
+    if public.len() + 1 != vk.ic.len() {
+        return Err(malformed(&format!(
+            "expected {} public signals, got {}",
+            vk.ic.len() - 1,
+            public.len()
+        )));
+    }
+
+    let vk_x = vk.ic[1..]
+        .iter()
+        .zip(public)
+        .fold(G1Projective::from(vk.ic[0]), |acc, (ic, x)| acc + *ic * x)
+        .into_affine();
+
+    let check = Bn254::multi_pairing(
+        [-proof.a, vk.alpha_1, vk_x, proof.c],
+        [proof.b, vk.beta_2, vk.gamma_2, vk.delta_2],
+    );
+
+    if check.0.is_one() {
+        Ok(())
+    } else {
+        Err(IssuerError::ProofRejected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    const P: &str = "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+    const R: &str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+    fn artifact(name: &str) -> Value {
+        read_json(&format!("{}/../{}", env!("CARGO_MANIFEST_DIR"), name)).unwrap()
+    }
+
+    fn vk() -> VerifyingKey {
+        VerifyingKey::from_json(&artifact("build/verification_key.json")).unwrap()
+    }
+
+    fn add(a: &str, b: &str) -> String {
+        (BigUint::from_str(a).unwrap() + BigUint::from_str(b).unwrap()).to_string()
+    }
+
+    #[test]
+    fn checked_in_proofs_verify() {
+        for (proof, public) in [
+            ("proof.json", "public.json"),
+            ("proof_bank.json", "public_bank.json"),
+            ("proof_insurance.json", "public_insurance.json"),
+        ] {
+            let pi = Proof::from_json(&artifact(proof)).unwrap();
+            let signals = public_inputs(&artifact(public)).unwrap();
+
+            assert!(verify(&vk(), &pi, &signals).is_ok(), "{} rejected", proof);
+        }
+    }
+
+    #[test]
+    fn rejects_spliced_proof() {
+        let mut v = artifact("proof.json");
+        v["pi_c"] = artifact("proof_bank.json")["pi_c"].clone();
+        let pi = Proof::from_json(&v).unwrap();
+
+        assert!(matches!(
+            verify(&vk(), &pi, &[]),
+            Err(IssuerError::ProofRejected)
+        ));
+    }
+
+    #[test]
+    fn rejects_off_curve_point() {
+        let mut v = artifact("proof.json");
+        v["pi_a"][1] = json!("5");
+
+        assert!(matches!(
+            Proof::from_json(&v),
+            Err(IssuerError::MalformedProof(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_non_canonical_coordinate() {
+        let mut v = artifact("proof.json");
+        let x = v["pi_a"][0].as_str().unwrap().to_string();
+        v["pi_a"][0] = json!(add(&x, P));
+
+        assert!(matches!(
+            Proof::from_json(&v),
+            Err(IssuerError::MalformedProof(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_non_affine_z() {
+        for (field, value) in [
+            ("pi_a", json!("2")),
+            ("pi_c", json!("12345")),
+            ("pi_b", json!(["2", "0"])),
+            ("pi_b", json!(["1", "1"])),
+        ] {
+            let mut v = artifact("proof.json");
+            v[field][2] = value;
+
+            assert!(
+                matches!(Proof::from_json(&v), Err(IssuerError::MalformedProof(_))),
+                "{} z accepted",
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_coordinate_count() {
+        let mut v = artifact("proof.json");
+        v["pi_b"].as_array_mut().unwrap().push(json!(["1", "0"]));
+        assert!(Proof::from_json(&v).is_err());
+
+        let mut v = artifact("proof.json");
+        v["pi_a"].as_array_mut().unwrap().push(json!("1"));
+        assert!(Proof::from_json(&v).is_err());
+
+        let mut v = artifact("proof.json");
+        v["pi_b"][0].as_array_mut().unwrap().push(json!("0"));
+        assert!(Proof::from_json(&v).is_err());
+    }
+
+    #[test]
+    fn infinity_only_in_snarkjs_form() {
+        assert_eq!(g1(&json!(["0", "1", "0"]), "p").unwrap(), G1Affine::zero());
+        assert_eq!(
+            g2(&json!([["0", "0"], ["1", "0"], ["0", "0"]]), "p").unwrap(),
+            G2Affine::zero()
+        );
+
+        assert!(g1(&json!(["0", "0", "0"]), "p").is_err());
+        assert!(g1(&json!(["5", "1", "0"]), "p").is_err());
+        assert!(g2(&json!([["0", "0"], ["0", "0"], ["0", "0"]]), "p").is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_public_signal_count() {
+        let pi = Proof::from_json(&artifact("proof.json")).unwrap();
+
+        assert!(matches!(
+            verify(&vk(), &pi, &[Fr::from(1u64)]),
+            Err(IssuerError::MalformedProof(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_non_canonical_signals() {
+        assert!(public_inputs(&json!([add(R, "1")])).is_err());
+        assert!(public_inputs(&json!([R])).is_err());
+        assert!(public_inputs(&json!(["+1"])).is_err());
+        assert!(public_inputs(&json!([""])).is_err());
+
+        let r_minus_1 =
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616";
+        assert_eq!(
+            public_inputs(&json!(["1", r_minus_1])).unwrap(),
+            vec![Fr::from(1u64), -Fr::from(1u64)]
+        );
+    }
+}